@@ -1,11 +1,64 @@
 use super::commandlist::*;
+use regex::Regex;
 use unicode_width::*;
 
+/// the direction an incremental search steps through matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Backward,
+}
+
+/// maximum number of undo snapshots we keep around, to bound memory usage
+const UNDO_HISTORY_DEPTH: usize = 500;
+
+/// a captured editor state: the lines together with the cursor position
+type Snapshot = (Vec<String>, usize, usize);
+
+/// the editing mode of the input field, mirroring vim's major modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorMode {
+    Insert,
+    Normal,
+    Visual,
+}
+
+impl EditorMode {
+    /// the short label shown in the input field title
+    pub fn label(self) -> &'static str {
+        match self {
+            EditorMode::Insert => "INSERT",
+            EditorMode::Normal => "NORMAL",
+            EditorMode::Visual => "VISUAL",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct EditorState {
     lines: Vec<String>,
     pub cursor_line: usize,
     pub cursor_col: usize,
+    pub mode: EditorMode,
+    undo_history: Vec<Snapshot>,
+    redo_history: Vec<Snapshot>,
+    /// whether the previous applied event inserted a character, used to
+    /// coalesce consecutive keystrokes into a single undo entry
+    last_was_insert: bool,
+    /// a half-typed operator in Normal mode (currently only `d`, for `dd`)
+    pending_operator: Option<char>,
+    /// the `(line, col)` the selection was started from in Visual mode
+    selection_anchor: Option<(usize, usize)>,
+    /// the internal yank/cut register
+    register: String,
+    /// byte ranges of the last search's matches in the joined buffer
+    search_matches: Vec<(usize, usize)>,
+    /// index into `search_matches` of the match the cursor last jumped to
+    current_match: Option<usize>,
+    /// the pattern `current_match` was computed against, so a repeated
+    /// search with the same pattern cycles from it instead of re-deriving a
+    /// match from the cursor position
+    last_search_pattern: String,
 }
 pub enum EditorEvent {
     NewCharacter(char),
@@ -17,9 +70,18 @@ pub enum EditorEvent {
     GoRight,
     GoUp,
     GoDown,
+    GoWordLeft,
+    GoWordRight,
     Home,
+    FirstNonBlank,
     End,
     KillWordBack,
+    DeleteLine,
+    Yank,
+    Cut,
+    Paste,
+    Undo,
+    Redo,
 }
 
 impl EditorState {
@@ -28,6 +90,267 @@ impl EditorState {
             lines: vec![String::new()],
             cursor_line: 0,
             cursor_col: 0,
+            mode: EditorMode::Insert,
+            undo_history: Vec::new(),
+            redo_history: Vec::new(),
+            last_was_insert: false,
+            pending_operator: None,
+            selection_anchor: None,
+            register: String::new(),
+            search_matches: Vec::new(),
+            current_match: None,
+            last_search_pattern: String::new(),
+        }
+    }
+
+    /// the cursor's absolute byte offset in the `\n`-joined buffer
+    fn cursor_offset(&self) -> usize {
+        let mut offset = 0;
+        for line in &self.lines[..self.cursor_line] {
+            offset += line.len() + 1;
+        }
+        offset + self.cursor_col
+    }
+
+    /// map an absolute byte offset in the `\n`-joined buffer back to a
+    /// `(line, col)` position
+    fn offset_to_linecol(&self, offset: usize) -> (usize, usize) {
+        let mut remaining = offset;
+        for (i, line) in self.lines.iter().enumerate() {
+            if remaining <= line.len() {
+                return (i, remaining);
+            }
+            remaining -= line.len() + 1;
+        }
+        let last = self.lines.len() - 1;
+        (last, self.lines[last].len())
+    }
+
+    /// Search the buffer for `pattern` (a regex), moving the cursor to the
+    /// start of a match in `direction` and wrapping around the ends.
+    /// Repeating the same pattern steps `current_match` forward/backward
+    /// through the match list (e.g. successive `n`/`N` presses); a changed
+    /// pattern re-derives the target match from the live cursor position
+    /// instead, as happens while typing an incremental search. Returns the
+    /// byte ranges of every match in the joined buffer so callers can
+    /// highlight all occurrences. An invalid regex clears the match set.
+    pub fn search(&mut self, pattern: &str, direction: Direction) -> Vec<(usize, usize)> {
+        let regex = match Regex::new(pattern) {
+            Ok(regex) => regex,
+            Err(_) => {
+                self.search_matches.clear();
+                self.current_match = None;
+                self.last_search_pattern = pattern.to_owned();
+                return Vec::new();
+            }
+        };
+        let repeated = self.last_search_pattern == pattern;
+        self.last_search_pattern = pattern.to_owned();
+        let joined = self.lines.join("\n");
+        self.search_matches = regex.find_iter(&joined).map(|m| (m.start(), m.end())).collect();
+        if self.search_matches.is_empty() {
+            self.current_match = None;
+            return Vec::new();
+        }
+        let chosen = match (repeated, self.current_match) {
+            (true, Some(prev)) => match direction {
+                Direction::Forward => (prev + 1) % self.search_matches.len(),
+                Direction::Backward => (prev + self.search_matches.len() - 1) % self.search_matches.len(),
+            },
+            _ => {
+                let cursor = self.cursor_offset();
+                match direction {
+                    Direction::Forward => {
+                        // start scanning just past the character under the cursor
+                        let after = cursor + (self.next_char_index() - self.cursor_col);
+                        self.search_matches.iter().position(|(start, _)| *start >= after).unwrap_or(0)
+                    }
+                    Direction::Backward => self
+                        .search_matches
+                        .iter()
+                        .rposition(|(start, _)| *start < cursor)
+                        .unwrap_or(self.search_matches.len() - 1),
+                }
+            }
+        };
+        self.current_match = Some(chosen);
+        let (start, _) = self.search_matches[chosen];
+        let (line, col) = self.offset_to_linecol(start);
+        self.cursor_line = line;
+        self.cursor_col = col;
+        self.search_matches.clone()
+    }
+
+    /// the ordered `(start, end)` `(line, col)` span of every current search
+    /// match, for highlighting in the UI. `end` points at the match's last
+    /// character, matching the inclusive-end convention of `selection_range`.
+    /// Empty matches (e.g. from a pattern like `a*`) are omitted.
+    pub fn search_matches(&self) -> Vec<((usize, usize), (usize, usize))> {
+        self.search_matches
+            .iter()
+            .filter(|(start, end)| end > start)
+            .map(|(start, end)| {
+                // step back from the match's last byte to the start of its last character
+                let (el, mut ec) = self.offset_to_linecol(*end - 1);
+                while !self.lines[el].is_char_boundary(ec) {
+                    ec -= 1;
+                }
+                (self.offset_to_linecol(*start), (el, ec))
+            })
+            .collect()
+    }
+
+    /// the active selection as an ordered, inclusive `(start, end)` pair of
+    /// `(line, col)` positions, or `None` when nothing is selected
+    pub fn selection_range(&self) -> Option<((usize, usize), (usize, usize))> {
+        let anchor = self.selection_anchor?;
+        let cursor = (self.cursor_line, self.cursor_col);
+        Some(if anchor <= cursor { (anchor, cursor) } else { (cursor, anchor) })
+    }
+
+    /// the byte index just past the character starting at `(line, col)`
+    fn byte_after(&self, line: usize, col: usize) -> usize {
+        let s = &self.lines[line];
+        if col >= s.len() {
+            s.len()
+        } else {
+            col + s[col..].chars().next().map(char::len_utf8).unwrap_or(0)
+        }
+    }
+
+    /// the text currently covered by the selection, joined with `\n`
+    fn selected_text(&self) -> Option<String> {
+        let ((sl, sc), (el, ec)) = self.selection_range()?;
+        let end = self.byte_after(el, ec);
+        if sl == el {
+            return Some(self.lines[sl][sc..end.max(sc)].to_owned());
+        }
+        let mut out = String::new();
+        out.push_str(&self.lines[sl][sc..]);
+        out.push('\n');
+        for line in &self.lines[sl + 1..el] {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.push_str(&self.lines[el][..end]);
+        Some(out)
+    }
+
+    /// delete the selected range, collapsing the remaining head and tail onto
+    /// a single line and clearing the selection
+    fn delete_selection(&mut self) {
+        if let Some(((sl, sc), (el, ec))) = self.selection_range() {
+            let end = self.byte_after(el, ec);
+            let tail = self.lines[el][end..].to_owned();
+            let head = self.lines[sl][..sc].to_owned();
+            self.lines.drain(sl + 1..=el);
+            self.lines[sl] = head + &tail;
+            self.cursor_line = sl;
+            self.cursor_col = sc.min(self.lines[sl].len());
+        }
+        self.selection_anchor = None;
+    }
+
+    /// push the register onto the system clipboard, when the `clipboard`
+    /// feature wires one in
+    fn copy_to_system_clipboard(&self) {
+        let _ = &self.register;
+        #[cfg(feature = "clipboard")]
+        {
+            use clipboard::{ClipboardContext, ClipboardProvider};
+            if let Ok(mut ctx) = ClipboardContext::new() {
+                let _ = ctx.set_contents(self.register.clone());
+            }
+        }
+    }
+
+    /// Interpret a single key press while in `Normal`/`Visual` mode, mapping
+    /// vim motions and operators onto the existing `EditorEvent`s. Insert-mode
+    /// typing is handled by the caller via `NewCharacter` as before.
+    pub fn apply_normal_key(&mut self, c: char) {
+        // resolve a pending two-key operator first (currently only `dd`)
+        if let Some(op) = self.pending_operator.take() {
+            if op == 'd' && c == 'd' {
+                self.apply_event(EditorEvent::DeleteLine);
+            }
+            return;
+        }
+        // operators that act on the Visual-mode selection
+        if self.mode == EditorMode::Visual {
+            match c {
+                'y' => {
+                    self.apply_event(EditorEvent::Yank);
+                    return;
+                }
+                'd' | 'x' => {
+                    self.apply_event(EditorEvent::Cut);
+                    return;
+                }
+                _ => {}
+            }
+        }
+        match c {
+            'h' => self.apply_event(EditorEvent::GoLeft),
+            'l' => self.apply_event(EditorEvent::GoRight),
+            'j' => self.apply_event(EditorEvent::GoDown),
+            'k' => self.apply_event(EditorEvent::GoUp),
+            'w' => self.apply_event(EditorEvent::GoWordRight),
+            'b' => self.apply_event(EditorEvent::GoWordLeft),
+            'x' => self.apply_event(EditorEvent::Delete),
+            '0' => self.apply_event(EditorEvent::Home),
+            '^' => self.apply_event(EditorEvent::FirstNonBlank),
+            '$' => self.apply_event(EditorEvent::End),
+            'u' => self.apply_event(EditorEvent::Undo),
+            // Ctrl+R, vim's conventional redo key
+            '\u{12}' => self.apply_event(EditorEvent::Redo),
+            'p' => self.apply_event(EditorEvent::Paste),
+            'd' => self.pending_operator = Some('d'),
+            'i' => self.mode = EditorMode::Insert,
+            'a' => {
+                self.apply_event(EditorEvent::GoRight);
+                self.mode = EditorMode::Insert;
+            }
+            'v' => {
+                if self.mode == EditorMode::Visual {
+                    self.mode = EditorMode::Normal;
+                    self.selection_anchor = None;
+                } else {
+                    self.mode = EditorMode::Visual;
+                    self.selection_anchor = Some((self.cursor_line, self.cursor_col));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// capture the current lines and cursor position
+    fn snapshot(&self) -> Snapshot {
+        (self.lines.clone(), self.cursor_line, self.cursor_col)
+    }
+
+    /// push the current state onto the undo stack and invalidate the redo
+    /// stack. keeps the undo stack bounded to `UNDO_HISTORY_DEPTH` entries.
+    fn push_undo(&mut self) {
+        self.undo_history.push(self.snapshot());
+        if self.undo_history.len() > UNDO_HISTORY_DEPTH {
+            self.undo_history.remove(0);
+        }
+        self.redo_history.clear();
+    }
+
+    /// restore a previously captured snapshot, re-establishing the
+    /// "at least one line" invariant and clamping the cursor into range.
+    /// Undo/redo doesn't capture the selection, so any open Visual selection
+    /// is dropped rather than left dangling against lines that may no longer
+    /// exist.
+    fn restore(&mut self, state: Snapshot) {
+        let (lines, cursor_line, cursor_col) = state;
+        self.lines = if lines.is_empty() { vec![String::new()] } else { lines };
+        self.cursor_line = cursor_line.min(self.lines.len() - 1);
+        self.cursor_col = cursor_col.min(self.lines[self.cursor_line].len());
+        self.selection_anchor = None;
+        if self.mode == EditorMode::Visual {
+            self.mode = EditorMode::Normal;
         }
     }
 
@@ -40,6 +363,10 @@ impl EditorState {
     }
 
     pub fn set_content(&mut self, new_content: &Vec<String>) {
+        self.push_undo();
+        self.last_was_insert = false;
+        self.search_matches.clear();
+        self.current_match = None;
         // prevent setting _no_ lines, which would crash
         self.lines = if new_content.is_empty() {
             vec![String::new()]
@@ -70,6 +397,12 @@ impl EditorState {
         &mut self.lines[self.cursor_line]
     }
 
+    /// the delimiters that separate words, shared by the word motions and
+    /// `KillWordBack`
+    fn is_word_delimiter(s: &str) -> bool {
+        matches!(s, " " | "/" | "\\" | ":" | "_" | "-")
+    }
+
     fn next_char_index(&self) -> usize {
         if self.cursor_col == self.current_line().len() {
             return self.cursor_col;
@@ -103,6 +436,40 @@ impl EditorState {
     }
 
     pub fn apply_event(&mut self, event: EditorEvent) {
+        // Snapshot the current state before mutating events so it can be
+        // undone. Runs of character insertions coalesce into a single undo
+        // entry: we only snapshot at the start of a run or on a word boundary.
+        // `Clear` and `load_commandentry` snapshot via `set_content`. These
+        // same events also invalidate any search match highlighting, since
+        // the byte offsets in `search_matches` are only meaningful against
+        // the buffer as it stood at the time of the search.
+        match &event {
+            EditorEvent::NewCharacter(c) => {
+                if !self.last_was_insert || *c == ' ' || *c == '/' || *c == ':' {
+                    self.push_undo();
+                }
+                self.search_matches.clear();
+                self.current_match = None;
+            }
+            EditorEvent::NewLine
+            | EditorEvent::Backspace
+            | EditorEvent::Delete
+            | EditorEvent::DeleteLine
+            | EditorEvent::Cut
+            | EditorEvent::Paste => {
+                self.push_undo();
+                self.search_matches.clear();
+                self.current_match = None;
+            }
+            EditorEvent::KillWordBack if !self.current_line().is_empty() => {
+                self.push_undo();
+                self.search_matches.clear();
+                self.current_match = None;
+            }
+            _ => {}
+        }
+        self.last_was_insert = matches!(event, EditorEvent::NewCharacter(_));
+
         match event {
             EditorEvent::NewCharacter(c) => {
                 let cursor_col = self.cursor_col;
@@ -146,6 +513,19 @@ impl EditorState {
                 self.set_content(&vec![String::new()]);
             }
 
+            EditorEvent::DeleteLine => {
+                if self.lines.len() == 1 {
+                    self.lines[0].clear();
+                    self.cursor_col = 0;
+                } else {
+                    self.lines.remove(self.cursor_line);
+                    if self.cursor_line >= self.lines.len() {
+                        self.cursor_line = self.lines.len() - 1;
+                    }
+                    self.cursor_col = self.cursor_col.min(self.current_line().len());
+                }
+            }
+
             EditorEvent::GoLeft => {
                 if self.cursor_col > 0 {
                     self.cursor_col = self.prev_char_index();
@@ -164,15 +544,126 @@ impl EditorState {
             }
             EditorEvent::GoUp if self.cursor_line > 0 => self.goto_line(self.cursor_line - 1),
             EditorEvent::GoDown if self.cursor_line < self.lines.len() - 1 => self.goto_line(self.cursor_line + 1),
+
+            EditorEvent::GoWordLeft => {
+                // skip any delimiters immediately to the left of the cursor
+                while self.cursor_col > 0
+                    && Self::is_word_delimiter(&self.current_line()[self.prev_char_index()..self.cursor_col])
+                {
+                    self.cursor_col = self.prev_char_index();
+                }
+                // walk back to the start of the word
+                while self.cursor_col > 0
+                    && !Self::is_word_delimiter(&self.current_line()[self.prev_char_index()..self.cursor_col])
+                {
+                    self.cursor_col = self.prev_char_index();
+                }
+            }
+            EditorEvent::GoWordRight => {
+                // skip the remainder of the word the cursor currently sits in
+                while self.cursor_col < self.current_line().len()
+                    && !Self::is_word_delimiter(&self.current_line()[self.cursor_col..self.next_char_index()])
+                {
+                    self.cursor_col = self.next_char_index();
+                }
+                // skip delimiters up to the start of the next word, crossing
+                // into the following line at end-of-line like GoRight
+                loop {
+                    if self.cursor_col >= self.current_line().len() {
+                        if self.cursor_line < self.lines.len() - 1 {
+                            self.cursor_line += 1;
+                            self.cursor_col = 0;
+                        } else {
+                            break;
+                        }
+                    }
+                    if self.cursor_col < self.current_line().len()
+                        && Self::is_word_delimiter(&self.current_line()[self.cursor_col..self.next_char_index()])
+                    {
+                        self.cursor_col = self.next_char_index();
+                    } else {
+                        break;
+                    }
+                }
+            }
             EditorEvent::Home => self.cursor_col = 0,
+            EditorEvent::FirstNonBlank => {
+                // the `^` motion: first non-whitespace byte, falling back to
+                // Home behavior when the line is blank. `char_indices` only
+                // ever yields valid char boundaries.
+                self.cursor_col = self
+                    .current_line()
+                    .char_indices()
+                    .find(|(_, c)| !c.is_whitespace())
+                    .map(|(i, _)| i)
+                    .unwrap_or(0);
+            }
+            // `0` and the end of the string are always valid char boundaries,
+            // so snapping through the char-index helpers is a no-op here.
             EditorEvent::End => self.cursor_col = self.current_line().len(),
 
+            EditorEvent::Yank => {
+                if let Some(text) = self.selected_text() {
+                    self.register = text;
+                    self.copy_to_system_clipboard();
+                }
+                self.selection_anchor = None;
+                if self.mode == EditorMode::Visual {
+                    self.mode = EditorMode::Normal;
+                }
+            }
+            EditorEvent::Cut => {
+                if let Some(text) = self.selected_text() {
+                    self.register = text;
+                    self.copy_to_system_clipboard();
+                    self.delete_selection();
+                }
+                if self.mode == EditorMode::Visual {
+                    self.mode = EditorMode::Normal;
+                }
+            }
+            EditorEvent::Paste => {
+                let text = self.register.clone();
+                let cursor_col = self.cursor_col;
+                let rest = self.current_line_mut().split_off(cursor_col);
+                let segments: Vec<&str> = text.split('\n').collect();
+                self.current_line_mut().push_str(segments[0]);
+                if segments.len() == 1 {
+                    self.cursor_col = self.current_line().len();
+                    self.current_line_mut().push_str(&rest);
+                } else {
+                    let mut insert_at = self.cursor_line + 1;
+                    for seg in &segments[1..] {
+                        self.lines.insert(insert_at, (*seg).to_owned());
+                        insert_at += 1;
+                    }
+                    self.cursor_line += segments.len() - 1;
+                    self.cursor_col = self.current_line().len();
+                    self.current_line_mut().push_str(&rest);
+                }
+            }
+
+            EditorEvent::Undo => {
+                if let Some(state) = self.undo_history.pop() {
+                    let current = self.snapshot();
+                    self.redo_history.push(current);
+                    self.restore(state);
+                }
+            }
+            EditorEvent::Redo => {
+                if let Some(state) = self.redo_history.pop() {
+                    let current = self.snapshot();
+                    self.undo_history.push(current);
+                    self.restore(state);
+                }
+            }
+
             EditorEvent::KillWordBack if !self.current_line().is_empty() => {
                 while let Some(c) = self.current_line().to_owned().get(self.prev_char_index()..self.cursor_col) {
                     let cursor_col = self.prev_char_index();
                     self.cursor_col = cursor_col;
                     self.current_line_mut().remove(cursor_col);
-                    if c == " " || c == "/" || c == "\\" || c == ":" || c == "_" || c == "-" || self.cursor_col == 0 {
+                    if Self::is_word_delimiter(c) || self.cursor_col == 0 {
                         break;
                     }
                 }
@@ -245,6 +736,246 @@ pub mod test {
         assert_eq!(le.displayed_cursor_column(), 5 as usize);
     }
 
+    #[test]
+    pub fn test_undo_redo() {
+        let mut le = EditorState::new();
+
+        // a run of characters coalesces into a single undo entry
+        le.apply_event(EditorEvent::NewCharacter('a'));
+        le.apply_event(EditorEvent::NewCharacter('b'));
+        le.apply_event(EditorEvent::NewCharacter('c'));
+        assert_eq!(le.content_str(), "abc");
+
+        le.apply_event(EditorEvent::Undo);
+        assert_eq!(le.content_str(), "");
+
+        le.apply_event(EditorEvent::Redo);
+        assert_eq!(le.content_str(), "abc");
+        assert_eq!(le.cursor_col, 3);
+
+        // undoing past an empty stack is a no-op
+        le.apply_event(EditorEvent::Undo);
+        le.apply_event(EditorEvent::Undo);
+        assert_eq!(le.content_str(), "");
+        assert_eq!(le.content_lines(), vec![""]);
+
+        // a word boundary starts a new undo group
+        le.apply_event(EditorEvent::NewCharacter('a'));
+        le.apply_event(EditorEvent::NewCharacter(' '));
+        le.apply_event(EditorEvent::NewCharacter('b'));
+        assert_eq!(le.content_str(), "a b");
+        // the space opened a new undo group capturing "a"
+        le.apply_event(EditorEvent::Undo);
+        assert_eq!(le.content_str(), "a");
+    }
+
+    #[test]
+    pub fn test_word_motions() {
+        let mut le = EditorState::new();
+        le.set_content(&vec!["foo/bar baz".to_string()]);
+        assert_eq!(le.cursor_col, 11);
+
+        le.apply_event(EditorEvent::GoWordLeft);
+        assert_eq!(le.cursor_col, 8); // start of "baz"
+        le.apply_event(EditorEvent::GoWordLeft);
+        assert_eq!(le.cursor_col, 4); // start of "bar"
+        le.apply_event(EditorEvent::GoWordLeft);
+        assert_eq!(le.cursor_col, 0); // start of "foo"
+        le.apply_event(EditorEvent::GoWordLeft);
+        assert_eq!(le.cursor_col, 0); // stays at column 0
+
+        le.apply_event(EditorEvent::GoWordRight);
+        assert_eq!(le.cursor_col, 4); // start of "bar"
+        le.apply_event(EditorEvent::GoWordRight);
+        assert_eq!(le.cursor_col, 8); // start of "baz"
+
+        // GoWordRight crosses into the next line at end-of-line
+        le.set_content(&vec!["ab".to_string(), "cd".to_string()]);
+        le.apply_event(EditorEvent::Home);
+        assert_eq!((le.cursor_line, le.cursor_col), (1, 0));
+        le.apply_event(EditorEvent::GoUp);
+        le.apply_event(EditorEvent::Home);
+        le.apply_event(EditorEvent::GoWordRight);
+        assert_eq!((le.cursor_line, le.cursor_col), (1, 0));
+    }
+
+    #[test]
+    pub fn test_first_non_blank() {
+        let mut le = EditorState::new();
+        le.set_content(&vec!["    ls -la".to_string()]);
+        le.apply_event(EditorEvent::FirstNonBlank);
+        assert_eq!(le.cursor_col, 4);
+
+        // a blank line falls back to Home
+        le.set_content(&vec!["     ".to_string()]);
+        le.apply_event(EditorEvent::FirstNonBlank);
+        assert_eq!(le.cursor_col, 0);
+    }
+
+    #[test]
+    pub fn test_normal_mode_keys() {
+        let mut le = EditorState::new();
+        le.set_content(&vec!["one two".to_string()]);
+        le.mode = EditorMode::Normal;
+
+        le.apply_event(EditorEvent::Home);
+        le.apply_normal_key('w');
+        assert_eq!(le.cursor_col, 4); // start of "two"
+        le.apply_normal_key('x');
+        assert_eq!(le.content_str(), "one wo");
+
+        // `dd` deletes the current line and `i` returns to Insert
+        le.set_content(&vec!["a".to_string(), "b".to_string()]);
+        le.mode = EditorMode::Normal;
+        le.apply_normal_key('d');
+        le.apply_normal_key('d');
+        assert_eq!(le.content_lines(), vec!["a"]);
+
+        le.apply_normal_key('i');
+        assert_eq!(le.mode, EditorMode::Insert);
+
+        // `u` undoes and Ctrl+R redoes, reachable from Normal mode
+        le.mode = EditorMode::Normal;
+        le.apply_normal_key('u');
+        assert_eq!(le.content_lines(), vec!["a", "b"]);
+        le.apply_normal_key('\u{12}');
+        assert_eq!(le.content_lines(), vec!["a"]);
+    }
+
+    #[test]
+    pub fn test_visual_yank_paste() {
+        let mut le = EditorState::new();
+        le.set_content(&vec!["hello world".to_string()]);
+        le.mode = EditorMode::Normal;
+        le.apply_event(EditorEvent::Home);
+
+        // select "hello" and yank it
+        le.apply_normal_key('v');
+        for _ in 0..4 {
+            le.apply_normal_key('l');
+        }
+        assert!(le.selection_range().is_some());
+        le.apply_normal_key('y');
+        assert_eq!(le.mode, EditorMode::Normal);
+        assert!(le.selection_range().is_none());
+
+        // paste the register at end of line
+        le.apply_event(EditorEvent::End);
+        le.apply_event(EditorEvent::Paste);
+        assert_eq!(le.content_str(), "hello worldhello");
+
+        // cut removes the selected range
+        le.set_content(&vec!["abcdef".to_string()]);
+        le.mode = EditorMode::Normal;
+        le.apply_event(EditorEvent::Home);
+        le.apply_normal_key('v');
+        le.apply_normal_key('l');
+        le.apply_normal_key('d');
+        assert_eq!(le.content_str(), "cdef");
+
+        // yank across a line break and paste the multi-line register
+        le.set_content(&vec!["abc".to_string(), "def".to_string(), "ghi".to_string()]);
+        le.mode = EditorMode::Normal;
+        le.apply_event(EditorEvent::Home);
+        le.apply_event(EditorEvent::GoUp);
+        le.apply_normal_key('l'); // (1, 1), on "def"'s "e"
+        le.apply_normal_key('v');
+        le.apply_normal_key('j'); // extend selection to (2, 1), on "ghi"'s "h"
+        assert_eq!(le.selection_range(), Some(((1, 1), (2, 1))));
+        le.apply_normal_key('y');
+        assert_eq!(le.mode, EditorMode::Normal);
+
+        le.apply_event(EditorEvent::End);
+        le.apply_event(EditorEvent::Paste);
+        assert_eq!(le.content_lines(), vec!["abc", "def", "ghief", "gh"]);
+    }
+
+    #[test]
+    pub fn test_undo_clears_stale_selection() {
+        let mut le = EditorState::new();
+        le.mode = EditorMode::Normal;
+
+        // build up a multi-line buffer; each character and newline below is
+        // its own undo entry (five in all)
+        le.apply_event(EditorEvent::NewCharacter('a'));
+        le.apply_event(EditorEvent::NewLine);
+        le.apply_event(EditorEvent::NewCharacter('b'));
+        le.apply_event(EditorEvent::NewLine);
+        le.apply_event(EditorEvent::NewCharacter('c'));
+        assert_eq!(le.content_lines(), vec!["a", "b", "c"]);
+
+        // open a selection anchored on the last line
+        le.apply_normal_key('v');
+        assert!(le.selection_range().is_some());
+
+        // undo past the point where that line existed
+        le.apply_normal_key('u');
+        le.apply_normal_key('u');
+        le.apply_normal_key('u');
+        le.apply_normal_key('u');
+        le.apply_normal_key('u');
+        assert_eq!(le.content_lines(), vec![""]);
+
+        // the stale selection must not survive the restore, so yanking is a
+        // no-op instead of panicking on an out-of-bounds line index
+        assert!(le.selection_range().is_none());
+        assert_eq!(le.mode, EditorMode::Normal);
+        le.apply_normal_key('y');
+        assert_eq!(le.content_lines(), vec![""]);
+
+        // redo likewise clears a selection opened after undoing
+        le.apply_normal_key('v');
+        assert!(le.selection_range().is_some());
+        le.apply_normal_key('\u{12}');
+        assert!(le.selection_range().is_none());
+        assert_eq!(le.mode, EditorMode::Normal);
+    }
+
+    #[test]
+    pub fn test_search() {
+        let mut le = EditorState::new();
+        le.set_content(&vec!["find -name foo".to_string(), "grep foo bar".to_string()]);
+
+        // move the cursor to the start of the buffer; forward search finds the first
+        le.apply_event(EditorEvent::Home);
+        le.apply_event(EditorEvent::GoUp);
+        le.apply_event(EditorEvent::Home);
+
+        let matches = le.search("foo", Direction::Forward);
+        assert_eq!(matches.len(), 2);
+        assert_eq!((le.cursor_line, le.cursor_col), (0, 11));
+
+        // a second forward step advances to the next occurrence
+        le.search("foo", Direction::Forward);
+        assert_eq!((le.cursor_line, le.cursor_col), (1, 5));
+
+        // forward again wraps back to the first occurrence
+        le.search("foo", Direction::Forward);
+        assert_eq!((le.cursor_line, le.cursor_col), (0, 11));
+
+        // backward steps to the previous (wrapping) occurrence
+        le.search("foo", Direction::Backward);
+        assert_eq!((le.cursor_line, le.cursor_col), (1, 5));
+
+        // an invalid regex clears the match set
+        assert!(le.search("(", Direction::Forward).is_empty());
+    }
+
+    #[test]
+    pub fn test_edit_invalidates_search_matches() {
+        let mut le = EditorState::new();
+        le.set_content(&vec!["find -name foo".to_string()]);
+
+        let matches = le.search("foo", Direction::Forward);
+        assert_eq!(matches.len(), 1);
+        assert!(!le.search_matches().is_empty());
+
+        // editing the buffer after a search must drop the now-stale matches,
+        // rather than leaving byte offsets that map onto the wrong text
+        le.apply_event(EditorEvent::NewCharacter('!'));
+        assert!(le.search_matches().is_empty());
+    }
+
     #[test]
     pub fn test_lineeditor_umlaut() {
         let mut le = EditorState::new();
@@ -1,8 +1,10 @@
 use super::app::*;
+use super::lineeditor::EditorMode;
 use std::io::{self, Stdout, Write};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use tui::layout::{Constraint, Direction, Layout, Rect};
 use tui::style::{Color, Modifier, Style};
-use tui::widgets::{Block, Borders, List, Paragraph, SelectableList, Text, Widget};
+use tui::widgets::{Block, Borders, Paragraph, SelectableList, Text, Widget};
 use tui::{backend::Backend, backend::CrosstermBackend, Frame, Terminal};
 use Constraint::*;
 
@@ -17,6 +19,135 @@ Ctrl+N     Next in history
 Config file is in
 ~/.config/pipr/pipr.toml";
 
+/// Split a logical line into visual rows no wider than `width` display
+/// columns, measuring with `unicode_width` so wide characters don't overflow.
+/// Always yields at least one (possibly empty) row.
+fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    wrap_line_with_offsets(line, width).into_iter().map(|(_, text)| text).collect()
+}
+
+/// like `wrap_line`, but also returns each row's starting byte offset within
+/// `line`, so callers can map a selection or search match's columns onto the
+/// specific wrapped row that displays them
+fn wrap_line_with_offsets(line: &str, width: usize) -> Vec<(usize, String)> {
+    if width == 0 {
+        return vec![(0, line.to_owned())];
+    }
+    let mut rows = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    let mut row_start = 0;
+    let mut byte_pos = 0;
+    for c in line.chars() {
+        let cw = c.width().unwrap_or(0);
+        if current_width + cw > width && current_width > 0 {
+            rows.push((row_start, std::mem::take(&mut current)));
+            current_width = 0;
+            row_start = byte_pos;
+        }
+        current.push(c);
+        current_width += cw;
+        byte_pos += c.len_utf8();
+    }
+    rows.push((row_start, current));
+    rows
+}
+
+/// the half-open byte range within `line` (index `idx` in the buffer) covered
+/// by a multi-line `(start, end)` position range such as `selection_range()`
+/// or one entry of `search_matches()`, or `None` if `idx` falls outside it.
+/// `end` is treated as inclusive of the character it points at, matching the
+/// `selection_range()`/`search_matches()` convention.
+fn line_subrange(sl: usize, sc: usize, el: usize, ec: usize, idx: usize, line: &str) -> Option<(usize, usize)> {
+    if idx < sl || idx > el {
+        return None;
+    }
+    let start = if idx == sl { sc } else { 0 };
+    let end = if idx == el {
+        if ec >= line.len() {
+            line.len()
+        } else {
+            ec + line[ec..].chars().next().map(char::len_utf8).unwrap_or(0)
+        }
+    } else {
+        line.len()
+    };
+    Some((start, end.max(start)))
+}
+
+/// whether byte offset `byte` falls inside the half-open `range`
+fn in_range(byte: usize, range: Option<(usize, usize)>) -> bool {
+    range.map_or(false, |(start, end)| byte >= start && byte < end)
+}
+
+/// how a single byte of the input field should be styled; a search match
+/// takes precedence over the selection when both cover the same byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Highlight {
+    Plain,
+    Selected,
+    SearchMatch,
+}
+
+fn highlight_at(byte: usize, matches: &[(usize, usize)], selection: Option<(usize, usize)>) -> Highlight {
+    if matches.iter().any(|range| in_range(byte, Some(*range))) {
+        Highlight::SearchMatch
+    } else if in_range(byte, selection) {
+        Highlight::Selected
+    } else {
+        Highlight::Plain
+    }
+}
+
+/// push `text` onto `spans`, styled according to `highlight`
+fn push_span(spans: &mut Vec<Text>, text: &str, highlight: Highlight) {
+    if text.is_empty() {
+        return;
+    }
+    match highlight {
+        Highlight::Plain => spans.push(Text::raw(text.to_owned())),
+        Highlight::Selected => {
+            spans.push(Text::styled(text.to_owned(), Style::default().bg(Color::Blue).fg(Color::Black)))
+        }
+        Highlight::SearchMatch => {
+            spans.push(Text::styled(text.to_owned(), Style::default().modifier(Modifier::REVERSED)))
+        }
+    }
+}
+
+/// Translate a cursor byte offset within a logical line into the
+/// `(row_offset, display_column)` of that cursor once the line is wrapped at
+/// `width` columns, mirroring the greedy logic of `wrap_line`.
+fn wrapped_cursor(line: &str, cursor_byte: usize, width: usize) -> (usize, usize) {
+    if width == 0 {
+        return (0, UnicodeWidthStr::width(&line[..cursor_byte]));
+    }
+    let mut row = 0;
+    let mut col = 0;
+    for (i, c) in line.char_indices() {
+        if i >= cursor_byte {
+            break;
+        }
+        let cw = c.width().unwrap_or(0);
+        if col + cw > width && col > 0 {
+            row += 1;
+            col = 0;
+        }
+        col += cw;
+    }
+    (row, col)
+}
+
+/// The number of visual rows the input field occupies once every logical line
+/// is wrapped at `width` columns.
+fn wrapped_row_count(app: &App, width: usize) -> usize {
+    app.input_state
+        .content_lines()
+        .iter()
+        .map(|line| wrap_line(line, width).len())
+        .sum()
+}
+
 fn make_default_block(title: &str, selected: bool) -> Block {
     let title_style = if selected {
         Style::default().fg(Color::Black).bg(Color::Cyan)
@@ -37,9 +168,13 @@ pub fn draw_app(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App
             .margin(1)
             .split(f.size());
 
+        // reserve as many rows as the command occupies once soft-wrapped to
+        // the panel width (2 extra rows for the surrounding border)
+        let wrap_width = (root_chunks[1].width as usize).saturating_sub(2);
+        let content_rows = wrapped_row_count(app, wrap_width);
         let exec_chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Length(2 + app.input_state.content_lines().len() as u16), Percentage(100)].as_ref())
+            .constraints([Length(2 + content_rows as u16), Percentage(100)].as_ref())
             .split(root_chunks[1]);
 
         input_field_rect = exec_chunks[0];
@@ -56,13 +191,28 @@ pub fn draw_app(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut App
         draw_outputs(&mut f, exec_chunks[1], &app.command_output, &app.command_error);
     })?;
 
-    // move cursor to where it belongs.
+    // move cursor to where it belongs, rendering a block cursor in the modal
+    // motion modes and a bar cursor while inserting (DECSCUSR escapes).
+    let cursor_shape = match app.input_state.mode {
+        EditorMode::Insert => "\x1b[6 q",
+        _ => "\x1b[2 q",
+    };
+    // translate the logical cursor position into wrapped (row, column) coords
+    let wrap_width = (input_field_rect.width as usize).saturating_sub(2);
+    let lines = app.input_state.content_lines();
+    let rows_above: usize = lines[..app.input_state.cursor_line]
+        .iter()
+        .map(|line| wrap_line(line, wrap_width).len())
+        .sum();
+    let (row_in_line, col_in_row) =
+        wrapped_cursor(app.input_state.current_line(), app.input_state.cursor_col, wrap_width);
     terminal.backend_mut().write(
         format!(
-            "{}",
+            "{}{}",
+            cursor_shape,
             crossterm::cursor::MoveTo(
-                input_field_rect.x + 1 + app.input_state.displayed_cursor_column() as u16,
-                input_field_rect.y + 1 + app.input_state.cursor_line as u16,
+                input_field_rect.x + 1 + col_in_row as u16,
+                input_field_rect.y + 1 + (rows_above + row_in_line) as u16,
             )
         )
         .as_bytes(),
@@ -83,17 +233,44 @@ fn draw_bookmark_list<B: Backend>(mut f: &mut Frame<B>, rect: Rect, is_focused:
 }
 
 fn draw_input_field<B: Backend>(mut f: &mut Frame<B>, rect: Rect, is_focused: bool, app: &App) {
-    let lines = app.input_state.content_lines().into_iter().map(|mut line| {
-        if line.len() > rect.width as usize - 5 {
-            line.truncate(rect.width as usize - 5);
-            line.push_str("...");
+    let wrap_width = (rect.width as usize).saturating_sub(2);
+    let selection = app.input_state.selection_range();
+    let search_matches = app.input_state.search_matches();
+
+    // soft-wrap each logical line into visual rows, slicing each row down to
+    // the selection's/each match's actual start/end columns instead of
+    // painting the whole row
+    let mut spans: Vec<Text> = Vec::new();
+    for (idx, line) in app.input_state.content_lines().iter().enumerate() {
+        let line_selection = selection.and_then(|((sl, sc), (el, ec))| line_subrange(sl, sc, el, ec, idx, line));
+        let line_matches: Vec<(usize, usize)> = search_matches
+            .iter()
+            .filter_map(|&((sl, sc), (el, ec))| line_subrange(sl, sc, el, ec, idx, line))
+            .collect();
+
+        for (row_start, row_text) in wrap_line_with_offsets(line, wrap_width) {
+            let mut run_start = 0usize;
+            let mut run_highlight = Highlight::Plain;
+            for (local_i, _) in row_text.char_indices() {
+                let highlight = highlight_at(row_start + local_i, &line_matches, line_selection);
+                if highlight != run_highlight {
+                    push_span(&mut spans, &row_text[run_start..local_i], run_highlight);
+                    run_start = local_i;
+                    run_highlight = highlight;
+                }
+            }
+            push_span(&mut spans, &row_text[run_start..], run_highlight);
+            spans.push(Text::raw("\n"));
         }
-        line
-    });
+    }
 
-    List::new(lines.map(Text::raw))
+    Paragraph::new(spans.iter())
         .block(make_default_block(
-            &format!("Command{}", if app.autoeval_mode { " [Autoeval]" } else { "" }),
+            &format!(
+                "Command [{}]{}",
+                app.input_state.mode.label(),
+                if app.autoeval_mode { " [Autoeval]" } else { "" }
+            ),
             is_focused,
         ))
         .render(&mut f, rect);